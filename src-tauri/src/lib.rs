@@ -1,33 +1,138 @@
 use std::{
-  io::{BufRead, BufReader, Read},
-  process::{Command, Stdio},
+  collections::HashMap,
+  io::{BufRead, BufReader, Read, Write},
+  process::{Child, ChildStdin, Command, Stdio},
   sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc, Condvar, Mutex,
   },
   thread,
+  time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tauri::{AppHandle, Emitter, State};
+use threadpool::ThreadPool;
 
 const EVENT_STREAM_CHUNK: &str = "qwen_stream_chunk";
 const EVENT_STREAM_ERROR: &str = "qwen_stream_error";
 const EVENT_FIRST_SEND_FAILED: &str = "qwen_first_send_failed";
+const EVENT_TOOL_EVENT: &str = "qwen_tool_event";
+const EVENT_APPROVAL_REQUEST: &str = "qwen_approval_request";
 
 const FIRST_SEND_FAILED_TITLE: &str = "qwen-cli 不可用";
 const FIRST_SEND_FAILED_MESSAGE: &str =
   "未检测到可用的 qwen-cli 或未登录。请在终端运行 qwen 并完成登录后重试。";
 
-#[derive(Default)]
+const DEFAULT_APPROVAL_TIMEOUT_MS: u64 = 120_000;
+
 struct QwenState {
-  manager: Mutex<QwenSessionManager>,
-  active_headless_jobs: Arc<AtomicUsize>,
+  // Per-session lock so one session's queued spawn (which can block on the spawn
+  // semaphore until another round's `qwen` process exits) can't stall another session's
+  // send/retry/status calls.
+  sessions: Mutex<HashMap<String, Arc<Mutex<SessionState>>>>,
+  round_progress: Arc<Mutex<HashMap<u64, RoundProgress>>>,
+  active_children: Arc<Mutex<HashMap<u64, ChildHandle>>>,
+  active_stdins: Arc<Mutex<HashMap<u64, Arc<Mutex<ChildStdin>>>>>,
+  pending_approvals: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+  next_round_id: Arc<AtomicU64>,
+  worker_pool: ThreadPool,
+  spawn_semaphore: Arc<Semaphore>,
+}
+
+impl QwenState {
+  fn new() -> Self {
+    let worker_threads = num_cpus::get().max(1);
+    log::info!("[mew] starting qwen headless worker pool with {worker_threads} thread(s)");
+
+    Self {
+      sessions: Mutex::new(HashMap::new()),
+      round_progress: Arc::new(Mutex::new(HashMap::new())),
+      active_children: Arc::new(Mutex::new(HashMap::new())),
+      active_stdins: Arc::new(Mutex::new(HashMap::new())),
+      pending_approvals: Arc::new(Mutex::new(HashMap::new())),
+      next_round_id: Arc::new(AtomicU64::new(1)),
+      worker_pool: ThreadPool::new(worker_threads),
+      spawn_semaphore: Semaphore::new(worker_threads),
+    }
+  }
+}
+
+/// Bounds how many native `qwen` child processes may be in flight at once, so a burst of
+/// sends queues at the call site instead of forking more processes than the pool can run.
+struct Semaphore {
+  permits: Mutex<usize>,
+  available: Condvar,
+}
+
+impl Semaphore {
+  fn new(permits: usize) -> Arc<Self> {
+    Arc::new(Self {
+      permits: Mutex::new(permits),
+      available: Condvar::new(),
+    })
+  }
+
+  /// Blocks the calling thread until a permit is free, then returns a guard that
+  /// releases the permit on drop.
+  fn acquire(self: &Arc<Self>) -> SpawnPermit {
+    let mut permits = self.permits.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    while *permits == 0 {
+      permits = self
+        .available
+        .wait(permits)
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+    *permits -= 1;
+
+    SpawnPermit { semaphore: self.clone() }
+  }
+
+  fn release(&self) {
+    let mut permits = self.permits.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *permits += 1;
+    self.available.notify_one();
+  }
+}
+
+struct SpawnPermit {
+  semaphore: Arc<Semaphore>,
+}
+
+impl Drop for SpawnPermit {
+  fn drop(&mut self) {
+    self.semaphore.release();
+  }
+}
+
+#[derive(Clone)]
+struct ChildHandle {
+  child: Arc<Mutex<Child>>,
+  cancelled: Arc<AtomicBool>,
+}
+
+impl ChildHandle {
+  fn kill(&self) -> std::io::Result<()> {
+    self.cancelled.store(true, Ordering::SeqCst);
+    self
+      .child
+      .lock()
+      .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "qwen child mutex poisoned"))?
+      .kill()
+  }
+}
+
+impl Drop for ChildHandle {
+  fn drop(&mut self) {
+    if let Ok(mut child) = self.child.lock() {
+      let _ = child.kill();
+    }
+  }
 }
 
 #[derive(Default)]
-struct QwenSessionManager {
+struct SessionState {
   first_send_attempted: bool,
   last_failed_input: Option<String>,
   last_failed_openai_config: Option<OpenAiConfig>,
@@ -36,20 +141,129 @@ struct QwenSessionManager {
   generation_round: u64,
 }
 
-struct ActiveHeadlessJobGuard {
-  counter: Arc<AtomicUsize>,
+fn session_handle(state: &QwenState, session_id: &str) -> Result<Arc<Mutex<SessionState>>, String> {
+  let mut sessions = state
+    .sessions
+    .lock()
+    .map_err(|_| "failed to lock qwen sessions".to_string())?;
+  Ok(
+    sessions
+      .entry(session_id.to_string())
+      .or_insert_with(|| Arc::new(Mutex::new(SessionState::default())))
+      .clone(),
+  )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum RoundState {
+  Queued,
+  Running,
+  Completed,
+  Failed,
+  Cancelled,
 }
 
-impl ActiveHeadlessJobGuard {
-  fn new(counter: Arc<AtomicUsize>) -> Self {
-    counter.fetch_add(1, Ordering::SeqCst);
-    Self { counter }
+impl RoundState {
+  fn is_active(self) -> bool {
+    matches!(self, RoundState::Queued | RoundState::Running)
   }
 }
 
-impl Drop for ActiveHeadlessJobGuard {
-  fn drop(&mut self) {
-    self.counter.fetch_sub(1, Ordering::SeqCst);
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RoundProgress {
+  round_id: u64,
+  session_id: String,
+  state: RoundState,
+  used_continue: bool,
+  started_at_ms: u64,
+  ended_at_ms: Option<u64>,
+  emitted_chunk_count: u64,
+  last_error_kind: Option<String>,
+  last_error_message: Option<String>,
+}
+
+impl RoundProgress {
+  fn new(round_id: u64, session_id: String, used_continue: bool) -> Self {
+    Self {
+      round_id,
+      session_id,
+      state: RoundState::Queued,
+      used_continue,
+      started_at_ms: now_ms(),
+      ended_at_ms: None,
+      emitted_chunk_count: 0,
+      last_error_kind: None,
+      last_error_message: None,
+    }
+  }
+}
+
+fn now_ms() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_millis() as u64)
+    .unwrap_or_default()
+}
+
+const MAX_FINISHED_ROUNDS_PER_SESSION: usize = 50;
+
+fn prune_finished_rounds(round_progress: &mut HashMap<u64, RoundProgress>, session_id: &str) {
+  let mut finished_round_ids: Vec<u64> = round_progress
+    .values()
+    .filter(|progress| progress.session_id == session_id && !progress.state.is_active())
+    .map(|progress| progress.round_id)
+    .collect();
+
+  if finished_round_ids.len() <= MAX_FINISHED_ROUNDS_PER_SESSION {
+    return;
+  }
+
+  finished_round_ids.sort_unstable();
+  let excess = finished_round_ids.len() - MAX_FINISHED_ROUNDS_PER_SESSION;
+  for round_id in finished_round_ids.into_iter().take(excess) {
+    round_progress.remove(&round_id);
+  }
+}
+
+fn finish_round_progress(
+  round_progress: &Mutex<HashMap<u64, RoundProgress>>,
+  round_id: u64,
+  state: RoundState,
+  error: Option<(&str, String)>,
+) {
+  let mut round_progress = match round_progress.lock() {
+    Ok(guard) => guard,
+    Err(_) => {
+      log::error!("[mew] qwen round_progress mutex poisoned while finishing round_id={round_id}");
+      return;
+    }
+  };
+  let Some(progress) = round_progress.get_mut(&round_id) else {
+    return;
+  };
+
+  progress.state = state;
+  progress.ended_at_ms = Some(now_ms());
+  if let Some((kind, message)) = error {
+    progress.last_error_kind = Some(kind.to_string());
+    progress.last_error_message = Some(message);
+  }
+
+  let session_id = progress.session_id.clone();
+  prune_finished_rounds(&mut round_progress, &session_id);
+}
+
+fn session_is_running(round_progress: &Mutex<HashMap<u64, RoundProgress>>, session_id: &str) -> bool {
+  match round_progress.lock() {
+    Ok(round_progress) => round_progress
+      .values()
+      .any(|progress| progress.session_id == session_id && progress.state.is_active()),
+    Err(_) => {
+      log::error!("[mew] qwen round_progress mutex poisoned while checking session_id={session_id}");
+      false
+    }
   }
 }
 
@@ -58,6 +272,7 @@ struct StreamSummary {
   emitted_any_chunk: bool,
   emitted_partial_chunk: bool,
   emitted_full_message: bool,
+  tool_call_names: HashMap<String, String>,
 }
 
 #[derive(Serialize)]
@@ -76,8 +291,42 @@ struct RetryAck {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 struct SessionStatus {
+  session_id: String,
   running: bool,
+  rounds: Vec<RoundProgress>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionSummary {
+  session_id: String,
+  running: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelAck {
+  ok: bool,
+  cancelled: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelAllAck {
+  ok: bool,
+  cancelled_count: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RespondAck {
+  ok: bool,
+  resolved: bool,
+  round_id: u64,
+  tool_call_id: String,
+  decision: String,
 }
 
 #[derive(Clone, Serialize)]
@@ -101,6 +350,25 @@ struct FirstSendFailedPayload {
   message: String,
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolCallEventPayload {
+  round_id: u64,
+  tool_name: String,
+  tool_call_id: String,
+  phase: String,
+  data: Value,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ApprovalRequestPayload {
+  round_id: u64,
+  tool_call_id: String,
+  tool_name: String,
+  input: Value,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct OpenAiConfig {
@@ -120,11 +388,12 @@ impl OpenAiConfig {
 struct HeadlessConfig {
   working_directory: Option<String>,
   approval_mode: Option<String>,
+  approval_timeout_ms: Option<u64>,
 }
 
 impl HeadlessConfig {
   fn is_empty(&self) -> bool {
-    self.working_directory.is_none() && self.approval_mode.is_none()
+    self.working_directory.is_none() && self.approval_mode.is_none() && self.approval_timeout_ms.is_none()
   }
 }
 
@@ -153,11 +422,16 @@ fn sanitize_approval_mode(mode: Option<String>) -> Option<String> {
   }
 }
 
+fn sanitize_approval_timeout_ms(timeout_ms: Option<u64>) -> Option<u64> {
+  timeout_ms.filter(|&ms| ms > 0)
+}
+
 fn sanitize_headless_config(config: Option<HeadlessConfig>) -> Option<HeadlessConfig> {
   config.and_then(|raw| {
     let sanitized = HeadlessConfig {
       working_directory: sanitize_optional_env_value(raw.working_directory),
       approval_mode: sanitize_approval_mode(raw.approval_mode),
+      approval_timeout_ms: sanitize_approval_timeout_ms(raw.approval_timeout_ms),
     };
 
     if sanitized.is_empty() {
@@ -275,11 +549,27 @@ fn build_qwen_headless_command(prompt: &str, use_continue: bool) -> Command {
   command
 }
 
-fn emit_stream_chunk(app: &AppHandle, round_id: u64, chunk: String) {
+fn emit_stream_chunk(
+  app: &AppHandle,
+  round_id: u64,
+  chunk: String,
+  round_progress: &Mutex<HashMap<u64, RoundProgress>>,
+) {
   if chunk.is_empty() {
     return;
   }
 
+  match round_progress.lock() {
+    Ok(mut round_progress) => {
+      if let Some(progress) = round_progress.get_mut(&round_id) {
+        progress.emitted_chunk_count = progress.emitted_chunk_count.saturating_add(1);
+      }
+    }
+    Err(_) => {
+      log::error!("[mew] qwen round_progress mutex poisoned while counting chunk for round_id={round_id}");
+    }
+  }
+
   let _ = app.emit(EVENT_STREAM_CHUNK, StreamChunkPayload { round_id, chunk });
 }
 
@@ -294,6 +584,44 @@ fn emit_stream_error(app: &AppHandle, round_id: u64, kind: &str, message: String
   );
 }
 
+fn emit_tool_event(app: &AppHandle, round_id: u64, tool_name: String, tool_call_id: String, phase: &str, data: Value) {
+  let _ = app.emit(
+    EVENT_TOOL_EVENT,
+    ToolCallEventPayload {
+      round_id,
+      tool_name,
+      tool_call_id,
+      phase: phase.to_string(),
+      data,
+    },
+  );
+}
+
+fn emit_approval_request(app: &AppHandle, round_id: u64, tool_call_id: String, tool_name: String, input: Value) {
+  let _ = app.emit(
+    EVENT_APPROVAL_REQUEST,
+    ApprovalRequestPayload {
+      round_id,
+      tool_call_id,
+      tool_name,
+      input,
+    },
+  );
+}
+
+fn write_approval_response(stdin: &Mutex<ChildStdin>, tool_call_id: &str, decision: &str) -> std::io::Result<()> {
+  let mut stdin = stdin
+    .lock()
+    .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "qwen stdin mutex poisoned"))?;
+  let line = serde_json::json!({
+    "type": "tool_approval_response",
+    "tool_use_id": tool_call_id,
+    "decision": decision,
+  });
+  writeln!(stdin, "{line}")?;
+  stdin.flush()
+}
+
 fn normalize_text_chunk(text: &str) -> Option<String> {
   if text.is_empty() {
     return None;
@@ -347,7 +675,59 @@ fn extract_message_text(content: &Value) -> Option<String> {
   }
 }
 
-fn extract_stream_chunk_from_json(value: &Value, summary: &mut StreamSummary) -> Option<String> {
+fn extract_approval_request(value: &Value) -> Option<(String, String, Value)> {
+  if value.get("type").and_then(Value::as_str) != Some("tool_approval_request") {
+    return None;
+  }
+
+  let tool_call_id = value.get("tool_use_id").and_then(Value::as_str)?.to_string();
+  let tool_name = value.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+  let input = value.get("input").cloned().unwrap_or(Value::Null);
+  Some((tool_call_id, tool_name, input))
+}
+
+fn extract_tool_use_blocks(content: &Value) -> Vec<(String, String, Value)> {
+  let Value::Array(items) = content else {
+    return Vec::new();
+  };
+
+  items
+    .iter()
+    .filter(|item| item.get("type").and_then(Value::as_str) == Some("tool_use"))
+    .map(|item| {
+      let name = item.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+      let id = item.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+      let input = item.get("input").cloned().unwrap_or(Value::Null);
+      (name, id, input)
+    })
+    .collect()
+}
+
+fn extract_stream_chunk_from_json(
+  app: &AppHandle,
+  round_id: u64,
+  value: &Value,
+  summary: &mut StreamSummary,
+) -> Option<String> {
+  let value_type = value.get("type").and_then(Value::as_str);
+
+  if value_type == Some("assistant") {
+    if let Some(content) = value.pointer("/message/content") {
+      for (tool_name, tool_call_id, input) in extract_tool_use_blocks(content) {
+        summary.tool_call_names.insert(tool_call_id.clone(), tool_name.clone());
+        emit_tool_event(app, round_id, tool_name, tool_call_id, "started", input);
+      }
+    }
+  } else if value_type == Some("tool_result") {
+    let tool_call_id = value.get("tool_use_id").and_then(Value::as_str).unwrap_or_default().to_string();
+    let tool_name = summary.tool_call_names.get(&tool_call_id).cloned().unwrap_or_default();
+    let is_error = value.get("is_error").and_then(Value::as_bool).unwrap_or(false);
+    let content = value.get("content").cloned().unwrap_or(Value::Null);
+    let phase = if is_error { "error" } else { "completed" };
+    emit_tool_event(app, round_id, tool_name, tool_call_id, phase, content);
+    return None;
+  }
+
   if let Some(event) = value.get("event") {
     if let Some(partial_text) = extract_partial_text_from_event(event) {
       summary.emitted_partial_chunk = true;
@@ -359,7 +739,6 @@ fn extract_stream_chunk_from_json(value: &Value, summary: &mut StreamSummary) ->
     return None;
   }
 
-  let value_type = value.get("type").and_then(Value::as_str);
   if value_type == Some("assistant") {
     let message_text = value
       .pointer("/message/content")
@@ -382,7 +761,67 @@ fn extract_stream_chunk_from_json(value: &Value, summary: &mut StreamSummary) ->
   None
 }
 
-fn stream_headless_stdout(app: &AppHandle, round_id: u64, stdout: impl Read) -> Result<StreamSummary, String> {
+fn handle_approval_request(
+  app: &AppHandle,
+  round_id: u64,
+  tool_call_id: String,
+  tool_name: String,
+  input: Value,
+  stdin: Arc<Mutex<ChildStdin>>,
+  pending_approvals: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+  timeout: Duration,
+) {
+  let resolved = Arc::new(AtomicBool::new(false));
+  let key = format!("{round_id}:{tool_call_id}");
+  match pending_approvals.lock() {
+    Ok(mut pending_approvals) => {
+      pending_approvals.insert(key.clone(), resolved.clone());
+    }
+    Err(_) => {
+      log::error!("[mew] qwen pending_approvals mutex poisoned while registering tool_call_id={tool_call_id}");
+    }
+  }
+
+  emit_approval_request(app, round_id, tool_call_id.clone(), tool_name, input);
+
+  let app = app.clone();
+  thread::spawn(move || {
+    thread::sleep(timeout);
+    if resolved.swap(true, Ordering::SeqCst) {
+      return;
+    }
+
+    match pending_approvals.lock() {
+      Ok(mut pending_approvals) => {
+        pending_approvals.remove(&key);
+      }
+      Err(_) => {
+        log::error!("[mew] qwen pending_approvals mutex poisoned while clearing timed-out tool_call_id={tool_call_id}");
+      }
+    }
+
+    log::warn!("[mew] qwen approval request timed out, round_id={round_id}, tool_call_id={tool_call_id}");
+    if let Err(err) = write_approval_response(&stdin, &tool_call_id, "deny") {
+      log::warn!("[mew] failed to write default-deny approval response, round_id={round_id}: {err}");
+    }
+    emit_stream_error(
+      &app,
+      round_id,
+      "approval_timeout",
+      format!("tool approval request timed out for tool_call_id={tool_call_id}"),
+    );
+  });
+}
+
+fn stream_headless_stdout(
+  app: &AppHandle,
+  round_id: u64,
+  stdout: impl Read,
+  stdin: Arc<Mutex<ChildStdin>>,
+  pending_approvals: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+  approval_timeout: Duration,
+  round_progress: &Mutex<HashMap<u64, RoundProgress>>,
+) -> Result<StreamSummary, String> {
   let mut reader = BufReader::new(stdout);
   let mut line = String::new();
   let mut summary = StreamSummary::default();
@@ -402,15 +841,29 @@ fn stream_headless_stdout(app: &AppHandle, round_id: u64, stdout: impl Read) ->
     }
 
     if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
-      if let Some(chunk) = extract_stream_chunk_from_json(&value, &mut summary) {
+      if let Some((tool_call_id, tool_name, input)) = extract_approval_request(&value) {
+        handle_approval_request(
+          app,
+          round_id,
+          tool_call_id,
+          tool_name,
+          input,
+          stdin.clone(),
+          pending_approvals.clone(),
+          approval_timeout,
+        );
+        continue;
+      }
+
+      if let Some(chunk) = extract_stream_chunk_from_json(app, round_id, &value, &mut summary) {
         summary.emitted_any_chunk = true;
-        emit_stream_chunk(app, round_id, chunk);
+        emit_stream_chunk(app, round_id, chunk, round_progress);
       }
       continue;
     }
 
     summary.emitted_any_chunk = true;
-    emit_stream_chunk(app, round_id, format!("{trimmed}\n"));
+    emit_stream_chunk(app, round_id, format!("{trimmed}\n"), round_progress);
   }
 
   Ok(summary)
@@ -424,26 +877,86 @@ fn read_stream_to_string(mut stream: impl Read) -> String {
   content
 }
 
+fn clear_pending_approvals_for_round(pending_approvals: &Mutex<HashMap<String, Arc<AtomicBool>>>, round_id: u64) {
+  let prefix = format!("{round_id}:");
+  match pending_approvals.lock() {
+    Ok(mut pending_approvals) => {
+      for (key, resolved) in pending_approvals.iter() {
+        if key.starts_with(&prefix) {
+          // Flip the flag so any in-flight timeout thread for this round sees itself as
+          // already resolved and skips writing a stale deny / emitting a stale timeout event.
+          resolved.store(true, Ordering::SeqCst);
+        }
+      }
+      pending_approvals.retain(|key, _| !key.starts_with(&prefix));
+    }
+    Err(_) => {
+      log::error!("[mew] qwen pending_approvals mutex poisoned while clearing round_id={round_id}");
+    }
+  }
+}
+
 fn spawn_headless_round(
   app: AppHandle,
   round_id: u64,
+  session_id: String,
   input: String,
   use_continue: bool,
   openai_config: Option<OpenAiConfig>,
   headless_config: Option<HeadlessConfig>,
-  active_headless_jobs: Arc<AtomicUsize>,
+  active_children: Arc<Mutex<HashMap<u64, ChildHandle>>>,
+  active_stdins: Arc<Mutex<HashMap<u64, Arc<Mutex<ChildStdin>>>>>,
+  pending_approvals: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+  round_progress: Arc<Mutex<HashMap<u64, RoundProgress>>>,
+  worker_pool: ThreadPool,
+  spawn_semaphore: Arc<Semaphore>,
 ) -> Result<(), String> {
+  let approval_timeout = Duration::from_millis(
+    headless_config
+      .as_ref()
+      .and_then(|config| config.approval_timeout_ms)
+      .unwrap_or(DEFAULT_APPROVAL_TIMEOUT_MS),
+  );
+
+  // Acquired before the native process is spawned and held until it exits, so a burst of
+  // sends queues here instead of forking more `qwen` processes than the pool can run.
+  let spawn_permit = spawn_semaphore.acquire();
+
   let mut command = build_qwen_headless_command(&input, use_continue);
   apply_qwen_cli_overrides(&mut command, headless_config.as_ref());
   apply_qwen_env_overrides(&mut command, openai_config.as_ref());
-  command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+  command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
 
   log::info!("[mew] spawning qwen headless process for round_id={round_id}, continue={use_continue}");
-  let mut child = command
-    .spawn()
-    .map_err(|err| format!("failed to start qwen headless process: {err}"))?;
+  let mut child = match command.spawn() {
+    Ok(child) => child,
+    Err(err) => {
+      let message = format!("failed to start qwen headless process: {err}");
+      match round_progress.lock() {
+        Ok(mut round_progress) => {
+          round_progress.insert(round_id, RoundProgress::new(round_id, session_id, use_continue));
+        }
+        Err(_) => {
+          log::error!("[mew] qwen round_progress mutex poisoned while recording spawn failure, round_id={round_id}");
+        }
+      }
+      // Route through finish_round_progress (rather than inserting an already-terminal
+      // entry directly) so repeated spawn failures still get pruned like any other round.
+      finish_round_progress(&round_progress, round_id, RoundState::Failed, Some(("spawn_failed", message.clone())));
+      return Err(message);
+    }
+  };
   log::info!("[mew] qwen headless process spawned for round_id={round_id}");
 
+  round_progress
+    .lock()
+    .map_err(|_| "qwen round_progress mutex poisoned".to_string())?
+    .insert(round_id, RoundProgress::new(round_id, session_id, use_continue));
+
+  let stdin = child
+    .stdin
+    .take()
+    .ok_or_else(|| "failed to capture qwen stdin".to_string())?;
   let stdout = child
     .stdout
     .take()
@@ -453,12 +966,73 @@ fn spawn_headless_round(
     .take()
     .ok_or_else(|| "failed to capture qwen stderr".to_string())?;
 
-  thread::spawn(move || {
-    let _active_job_guard = ActiveHeadlessJobGuard::new(active_headless_jobs);
+  let stdin = Arc::new(Mutex::new(stdin));
+  active_stdins
+    .lock()
+    .map_err(|_| "qwen active_stdins mutex poisoned".to_string())?
+    .insert(round_id, stdin.clone());
+
+  let child_handle = ChildHandle {
+    child: Arc::new(Mutex::new(child)),
+    cancelled: Arc::new(AtomicBool::new(false)),
+  };
+  active_children
+    .lock()
+    .map_err(|_| "qwen active_children mutex poisoned".to_string())?
+    .insert(round_id, child_handle.clone());
+
+  worker_pool.execute(move || {
+    let _spawn_permit = spawn_permit;
+
+    match round_progress.lock() {
+      Ok(mut round_progress) => {
+        if let Some(progress) = round_progress.get_mut(&round_id) {
+          progress.state = RoundState::Running;
+        }
+      }
+      Err(_) => {
+        log::error!("[mew] qwen round_progress mutex poisoned while marking round_id={round_id} running");
+      }
+    }
 
     let stderr_handle = thread::spawn(move || read_stream_to_string(stderr));
-    let summary = stream_headless_stdout(&app, round_id, stdout);
-    let wait_result = child.wait();
+    let summary = stream_headless_stdout(
+      &app,
+      round_id,
+      stdout,
+      stdin,
+      pending_approvals.clone(),
+      approval_timeout,
+      &round_progress,
+    );
+    let wait_result = match child_handle.child.lock() {
+      Ok(mut child) => child.wait(),
+      Err(_) => {
+        log::error!("[mew] qwen child mutex poisoned while waiting on round_id={round_id}");
+        let message = "qwen child mutex poisoned".to_string();
+        emit_stream_error(&app, round_id, "internal_error", message.clone());
+        finish_round_progress(&round_progress, round_id, RoundState::Failed, Some(("internal_error", message)));
+        return;
+      }
+    };
+    let cancelled = child_handle.cancelled.load(Ordering::SeqCst);
+    match active_children.lock() {
+      Ok(mut active_children) => {
+        active_children.remove(&round_id);
+      }
+      Err(_) => {
+        log::error!("[mew] qwen active_children mutex poisoned while cleaning up round_id={round_id}");
+      }
+    }
+    match active_stdins.lock() {
+      Ok(mut active_stdins) => {
+        active_stdins.remove(&round_id);
+      }
+      Err(_) => {
+        log::error!("[mew] qwen active_stdins mutex poisoned while cleaning up round_id={round_id}");
+      }
+    }
+    clear_pending_approvals_for_round(&pending_approvals, round_id);
 
     let stderr_output = stderr_handle.join().unwrap_or_else(|_| String::new());
 
@@ -466,7 +1040,8 @@ fn spawn_headless_round(
       Ok(summary) => summary,
       Err(err) => {
         log::warn!("[mew] failed streaming qwen output for round_id={round_id}: {err}");
-        emit_stream_error(&app, round_id, "stdout_read_error", err);
+        emit_stream_error(&app, round_id, "stdout_read_error", err.clone());
+        finish_round_progress(&round_progress, round_id, RoundState::Failed, Some(("stdout_read_error", err)));
         return;
       }
     };
@@ -479,11 +1054,19 @@ fn spawn_headless_round(
             format!("qwen completed without stdout. stderr: {}", stderr_output.trim())
           };
           log::warn!("[mew] qwen returned no output for round_id={round_id}: {message}");
-          emit_stream_error(&app, round_id, "empty_output", message);
+          emit_stream_error(&app, round_id, "empty_output", message.clone());
+          finish_round_progress(&round_progress, round_id, RoundState::Completed, Some(("empty_output", message)));
         } else {
           log::info!("[mew] qwen headless round completed, round_id={round_id}");
+          finish_round_progress(&round_progress, round_id, RoundState::Completed, None);
         }
       }
+      Ok(_status) if cancelled => {
+        log::info!("[mew] qwen headless round cancelled, round_id={round_id}");
+        let message = "qwen headless round was cancelled".to_string();
+        emit_stream_error(&app, round_id, "cancelled", message.clone());
+        finish_round_progress(&round_progress, round_id, RoundState::Cancelled, Some(("cancelled", message)));
+      }
       Ok(status) => {
         let status_code = status
           .code()
@@ -498,12 +1081,14 @@ fn spawn_headless_round(
           )
         };
         log::warn!("[mew] qwen headless round failed, round_id={round_id}: {message}");
-        emit_stream_error(&app, round_id, "command_failed", message);
+        emit_stream_error(&app, round_id, "command_failed", message.clone());
+        finish_round_progress(&round_progress, round_id, RoundState::Failed, Some(("command_failed", message)));
       }
       Err(err) => {
         let message = format!("failed waiting qwen process: {err}");
         log::warn!("[mew] qwen headless wait failed for round_id={round_id}: {message}");
-        emit_stream_error(&app, round_id, "wait_error", message);
+        emit_stream_error(&app, round_id, "wait_error", message.clone());
+        finish_round_progress(&round_progress, round_id, RoundState::Failed, Some(("wait_error", message)));
       }
     }
   });
@@ -515,50 +1100,60 @@ fn spawn_headless_round(
 fn qwen_send(
   app: AppHandle,
   state: State<'_, QwenState>,
+  session_id: String,
   input: String,
   openai_config: Option<OpenAiConfig>,
   headless_config: Option<HeadlessConfig>,
 ) -> Result<SendAck, String> {
-  let mut manager = state
-    .manager
+  let session_handle = session_handle(&state, &session_id)?;
+  let mut session = session_handle
     .lock()
-    .map_err(|_| "failed to lock qwen manager".to_string())?;
+    .map_err(|_| "failed to lock qwen session".to_string())?;
 
-  let is_first_attempt = !manager.first_send_attempted;
-  manager.first_send_attempted = true;
-  manager.generation_round = manager.generation_round.saturating_add(1);
-  let round_id = manager.generation_round;
+  let is_first_attempt = !session.first_send_attempted;
+  session.first_send_attempted = true;
+  session.generation_round = session.generation_round.saturating_add(1);
+  let session_round_index = session.generation_round;
+  let round_id = state.next_round_id.fetch_add(1, Ordering::SeqCst);
   let sanitized_openai_config = sanitize_openai_config(openai_config);
   let sanitized_headless_config = sanitize_headless_config(headless_config);
 
-  let use_continue = round_id > 1 && manager.session_headless_config == sanitized_headless_config;
-  if round_id > 1 && !use_continue {
-    log::info!("[mew] headless config changed; restarting qwen session without --continue, round_id={round_id}");
+  let use_continue = session_round_index > 1 && session.session_headless_config == sanitized_headless_config;
+  if session_round_index > 1 && !use_continue {
+    log::info!(
+      "[mew] headless config changed; restarting qwen session without --continue, session_id={session_id}, round_id={round_id}"
+    );
   }
   match spawn_headless_round(
     app.clone(),
     round_id,
+    session_id.clone(),
     input.clone(),
     use_continue,
     sanitized_openai_config.clone(),
     sanitized_headless_config.clone(),
-    state.active_headless_jobs.clone(),
+    state.active_children.clone(),
+    state.active_stdins.clone(),
+    state.pending_approvals.clone(),
+    state.round_progress.clone(),
+    state.worker_pool.clone(),
+    state.spawn_semaphore.clone(),
   ) {
     Ok(()) => {
-      manager.last_failed_input = None;
-      manager.last_failed_openai_config = None;
-      manager.last_failed_headless_config = None;
+      session.last_failed_input = None;
+      session.last_failed_openai_config = None;
+      session.last_failed_headless_config = None;
       if !use_continue {
-        manager.session_headless_config = sanitized_headless_config.clone();
+        session.session_headless_config = sanitized_headless_config.clone();
       }
-      log::info!("[mew] qwen_send accepted in headless mode, round_id={round_id}");
+      log::info!("[mew] qwen_send accepted in headless mode, session_id={session_id}, round_id={round_id}");
       Ok(SendAck { ok: true, round_id })
     }
     Err(err) => {
-      manager.last_failed_input = Some(input);
-      manager.last_failed_openai_config = sanitized_openai_config;
-      manager.last_failed_headless_config = sanitized_headless_config;
-      log::warn!("[mew] qwen_send failed in headless mode: {err}");
+      session.last_failed_input = Some(input);
+      session.last_failed_openai_config = sanitized_openai_config;
+      session.last_failed_headless_config = sanitized_headless_config;
+      log::warn!("[mew] qwen_send failed in headless mode, session_id={session_id}: {err}");
 
       if is_first_attempt {
         let _ = app.emit(
@@ -576,13 +1171,13 @@ fn qwen_send(
 }
 
 #[tauri::command]
-fn qwen_retry_last(app: AppHandle, state: State<'_, QwenState>) -> Result<RetryAck, String> {
-  let mut manager = state
-    .manager
+fn qwen_retry_last(app: AppHandle, state: State<'_, QwenState>, session_id: String) -> Result<RetryAck, String> {
+  let session_handle = session_handle(&state, &session_id)?;
+  let mut session = session_handle
     .lock()
-    .map_err(|_| "failed to lock qwen manager".to_string())?;
+    .map_err(|_| "failed to lock qwen session".to_string())?;
 
-  let Some(last_input) = manager.last_failed_input.clone() else {
+  let Some(last_input) = session.last_failed_input.clone() else {
     return Ok(RetryAck {
       ok: true,
       resent: false,
@@ -590,27 +1185,34 @@ fn qwen_retry_last(app: AppHandle, state: State<'_, QwenState>) -> Result<RetryA
     });
   };
 
-  manager.generation_round = manager.generation_round.saturating_add(1);
-  let round_id = manager.generation_round;
-  let last_openai_config = manager.last_failed_openai_config.clone();
-  let last_headless_config = manager.last_failed_headless_config.clone();
+  session.generation_round = session.generation_round.saturating_add(1);
+  let session_round_index = session.generation_round;
+  let round_id = state.next_round_id.fetch_add(1, Ordering::SeqCst);
+  let last_openai_config = session.last_failed_openai_config.clone();
+  let last_headless_config = session.last_failed_headless_config.clone();
 
-  let use_continue = round_id > 1 && manager.session_headless_config == last_headless_config;
+  let use_continue = session_round_index > 1 && session.session_headless_config == last_headless_config;
   match spawn_headless_round(
     app,
     round_id,
+    session_id.clone(),
     last_input.clone(),
     use_continue,
     last_openai_config.clone(),
     last_headless_config.clone(),
-    state.active_headless_jobs.clone(),
+    state.active_children.clone(),
+    state.active_stdins.clone(),
+    state.pending_approvals.clone(),
+    state.round_progress.clone(),
+    state.worker_pool.clone(),
+    state.spawn_semaphore.clone(),
   ) {
     Ok(()) => {
-      manager.last_failed_input = None;
-      manager.last_failed_openai_config = None;
-      manager.last_failed_headless_config = None;
+      session.last_failed_input = None;
+      session.last_failed_openai_config = None;
+      session.last_failed_headless_config = None;
       if !use_continue {
-        manager.session_headless_config = last_headless_config.clone();
+        session.session_headless_config = last_headless_config.clone();
       }
       Ok(RetryAck {
         ok: true,
@@ -619,9 +1221,9 @@ fn qwen_retry_last(app: AppHandle, state: State<'_, QwenState>) -> Result<RetryA
       })
     }
     Err(_) => {
-      manager.last_failed_input = Some(last_input);
-      manager.last_failed_openai_config = last_openai_config;
-      manager.last_failed_headless_config = last_headless_config;
+      session.last_failed_input = Some(last_input);
+      session.last_failed_openai_config = last_openai_config;
+      session.last_failed_headless_config = last_headless_config;
       Ok(RetryAck {
         ok: false,
         resent: false,
@@ -632,16 +1234,183 @@ fn qwen_retry_last(app: AppHandle, state: State<'_, QwenState>) -> Result<RetryA
 }
 
 #[tauri::command]
-fn qwen_status(state: State<'_, QwenState>) -> Result<SessionStatus, String> {
+fn qwen_status(
+  state: State<'_, QwenState>,
+  session_id: String,
+  active_only: Option<bool>,
+) -> Result<SessionStatus, String> {
+  let round_progress = state
+    .round_progress
+    .lock()
+    .map_err(|_| "failed to lock qwen round_progress".to_string())?;
+
+  let active_only = active_only.unwrap_or(false);
+  let mut rounds: Vec<RoundProgress> = round_progress
+    .values()
+    .filter(|progress| progress.session_id == session_id)
+    .filter(|progress| !active_only || progress.state.is_active())
+    .cloned()
+    .collect();
+  rounds.sort_by_key(|progress| progress.round_id);
+
+  let running = rounds.iter().any(|progress| progress.state.is_active());
+
   Ok(SessionStatus {
-    running: state.active_headless_jobs.load(Ordering::SeqCst) > 0,
+    session_id,
+    running,
+    rounds,
+  })
+}
+
+#[tauri::command]
+fn qwen_list_sessions(state: State<'_, QwenState>) -> Result<Vec<SessionSummary>, String> {
+  let sessions = state
+    .sessions
+    .lock()
+    .map_err(|_| "failed to lock qwen sessions".to_string())?;
+
+  Ok(
+    sessions
+      .keys()
+      .map(|session_id| SessionSummary {
+        running: session_is_running(&state.round_progress, session_id),
+        session_id: session_id.clone(),
+      })
+      .collect(),
+  )
+}
+
+#[tauri::command]
+fn qwen_cancel(state: State<'_, QwenState>, round_id: u64) -> Result<CancelAck, String> {
+  let children = state
+    .active_children
+    .lock()
+    .map_err(|_| "failed to lock qwen active_children".to_string())?;
+
+  let Some(handle) = children.get(&round_id) else {
+    return Ok(CancelAck {
+      ok: true,
+      cancelled: false,
+    });
+  };
+
+  match handle.kill() {
+    Ok(()) => {
+      log::info!("[mew] qwen_cancel killed round_id={round_id}");
+      Ok(CancelAck {
+        ok: true,
+        cancelled: true,
+      })
+    }
+    Err(err) => {
+      log::warn!("[mew] qwen_cancel failed to kill round_id={round_id}: {err}");
+      Err(format!("failed to cancel qwen round: {err}"))
+    }
+  }
+}
+
+#[tauri::command]
+fn qwen_cancel_all(state: State<'_, QwenState>) -> Result<CancelAllAck, String> {
+  let children = state
+    .active_children
+    .lock()
+    .map_err(|_| "failed to lock qwen active_children".to_string())?;
+
+  let mut cancelled_count = 0usize;
+  for (round_id, handle) in children.iter() {
+    match handle.kill() {
+      Ok(()) => cancelled_count += 1,
+      Err(err) => log::warn!("[mew] qwen_cancel_all failed to kill round_id={round_id}: {err}"),
+    }
+  }
+
+  log::info!("[mew] qwen_cancel_all killed {cancelled_count} round(s)");
+  Ok(CancelAllAck {
+    ok: true,
+    cancelled_count,
+  })
+}
+
+#[tauri::command]
+fn qwen_respond(
+  state: State<'_, QwenState>,
+  round_id: u64,
+  tool_call_id: String,
+  decision: String,
+) -> Result<RespondAck, String> {
+  let sanitized_decision = match decision.as_str() {
+    "allow" | "deny" => decision,
+    other => return Err(format!("unsupported qwen approval decision: {other}")),
+  };
+
+  let key = format!("{round_id}:{tool_call_id}");
+  let resolved_flag = state
+    .pending_approvals
+    .lock()
+    .map_err(|_| "failed to lock qwen pending_approvals".to_string())?
+    .get(&key)
+    .cloned();
+
+  let Some(resolved_flag) = resolved_flag else {
+    return Ok(RespondAck {
+      ok: true,
+      resolved: false,
+      round_id,
+      tool_call_id,
+      decision: sanitized_decision,
+    });
+  };
+
+  if resolved_flag.swap(true, Ordering::SeqCst) {
+    return Ok(RespondAck {
+      ok: true,
+      resolved: false,
+      round_id,
+      tool_call_id,
+      decision: sanitized_decision,
+    });
+  }
+
+  state
+    .pending_approvals
+    .lock()
+    .map_err(|_| "failed to lock qwen pending_approvals".to_string())?
+    .remove(&key);
+
+  let stdin = state
+    .active_stdins
+    .lock()
+    .map_err(|_| "failed to lock qwen active_stdins".to_string())?
+    .get(&round_id)
+    .cloned();
+
+  let Some(stdin) = stdin else {
+    return Ok(RespondAck {
+      ok: false,
+      resolved: true,
+      round_id,
+      tool_call_id,
+      decision: sanitized_decision,
+    });
+  };
+
+  write_approval_response(&stdin, &tool_call_id, &sanitized_decision)
+    .map_err(|err| format!("failed to write qwen approval response: {err}"))?;
+
+  log::info!("[mew] qwen_respond wrote decision={sanitized_decision} for round_id={round_id}, tool_call_id={tool_call_id}");
+  Ok(RespondAck {
+    ok: true,
+    resolved: true,
+    round_id,
+    tool_call_id,
+    decision: sanitized_decision,
   })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
-    .manage(QwenState::default())
+    .manage(QwenState::new())
     .setup(|app| {
       if cfg!(debug_assertions) {
         app.handle().plugin(
@@ -653,7 +1422,15 @@ pub fn run() {
 
       Ok(())
     })
-    .invoke_handler(tauri::generate_handler![qwen_send, qwen_retry_last, qwen_status])
+    .invoke_handler(tauri::generate_handler![
+      qwen_send,
+      qwen_retry_last,
+      qwen_status,
+      qwen_list_sessions,
+      qwen_cancel,
+      qwen_cancel_all,
+      qwen_respond
+    ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
 }